@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_shell::process::CommandEvent;
+use tauri_plugin_shell::ShellExt;
+use tokio_util::sync::CancellationToken;
+
+/// Monotonic identifier handed back to the frontend for each spawned job.
+pub type JobId = u64;
+
+/// Command the frontend asks us to run — typically the training or
+/// dataset-prep script plus its arguments. Threaded straight into
+/// `tauri_plugin_shell` so the child inherits the sandbox's shell scope.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrainingConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// Lifecycle of a job as observed from the outside.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Snapshot returned by [`get_job_status`]. Kept behind an `Arc<Mutex<_>>` so
+/// the running task can update it in place while the command reads it.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatus {
+    pub id: JobId,
+    pub state: JobState,
+    pub step: u64,
+    pub total: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_loss: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+}
+
+struct JobEntry {
+    token: CancellationToken,
+    #[allow(dead_code)]
+    handle: JoinHandle<()>,
+    status: Arc<Mutex<JobStatus>>,
+}
+
+/// Registry of in-flight jobs held in Tauri managed state.
+///
+/// Finished jobs are retained so their final status stays queryable, but only
+/// up to [`MAX_RETAINED_JOBS`]; older terminal entries are reaped as new ones
+/// complete, so a long session that runs many trainings doesn't grow the map
+/// without bound.
+#[derive(Default)]
+pub struct JobManager {
+    next: AtomicU64,
+    jobs: Mutex<HashMap<JobId, JobEntry>>,
+}
+
+/// How many finished (completed/failed) jobs to keep around for status queries.
+const MAX_RETAINED_JOBS: usize = 32;
+
+/// Drop the oldest terminal entries so at most [`MAX_RETAINED_JOBS`] finished
+/// jobs are retained. Running jobs are never reaped.
+fn reap_finished<R: Runtime>(app: &AppHandle<R>) {
+    let manager = app.state::<JobManager>();
+    let mut jobs = manager.jobs.lock().unwrap();
+    let mut finished: Vec<JobId> = jobs
+        .iter()
+        .filter(|(_, entry)| entry.status.lock().unwrap().state != JobState::Running)
+        .map(|(id, _)| *id)
+        .collect();
+    if finished.len() > MAX_RETAINED_JOBS {
+        // Ids are monotonic, so sorting ascending puts the oldest first.
+        finished.sort_unstable();
+        for id in &finished[..finished.len() - MAX_RETAINED_JOBS] {
+            jobs.remove(id);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LogEvent {
+    job_id: JobId,
+    stream: &'static str,
+    line: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ProgressEvent {
+    job_id: JobId,
+    step: u64,
+    total: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    loss: Option<f64>,
+}
+
+/// Best-effort parse of `step`/`total`/`loss` out of a training log line.
+///
+/// Recognises `step 12/1000` (spaces around the slash tolerated) and
+/// `loss=0.1234` / `loss: 0.1234`, anchored on whole words so `timestep` and
+/// `loss_scale` don't mis-match. The `step` keyword may be pluralised
+/// (`steps 3/10`). Anything it can't read is left as `None` so the line still
+/// shows up in the log stream.
+fn parse_progress(line: &str) -> (Option<(u64, u64)>, Option<f64>) {
+    let lower = line.to_lowercase();
+    let step = find_keyword(&lower, "step").and_then(|idx| parse_fraction(&lower[idx..]));
+    let loss = find_keyword(&lower, "loss").and_then(|idx| parse_number(&lower[idx..]));
+    (step, loss)
+}
+
+/// Find `keyword` as a whole word (an optional trailing `s` is allowed) and
+/// return the byte index just past it. Rejects matches where an alphanumeric
+/// char abuts either side or an `_` follows, so `timestep`/`steps_per` don't
+/// anchor on `step`.
+fn find_keyword(haystack: &str, keyword: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(rel) = haystack[from..].find(keyword) {
+        let start = from + rel;
+        let end = start + keyword.len();
+        let prev_ok = start == 0
+            || !haystack[..start]
+                .chars()
+                .next_back()
+                .unwrap()
+                .is_alphanumeric();
+        // Accept a pluralised keyword ("steps").
+        let after = if haystack[end..].starts_with('s') {
+            end + 1
+        } else {
+            end
+        };
+        let next_ok = haystack[after..]
+            .chars()
+            .next()
+            .map_or(true, |c| !c.is_alphanumeric() && c != '_');
+        if prev_ok && next_ok {
+            return Some(after);
+        }
+        from = end;
+    }
+    None
+}
+
+/// Parse a `current/total` fraction from the start of `s`, tolerating
+/// separators (`:`/`=`) and whitespace around the slash.
+fn parse_fraction(s: &str) -> Option<(u64, u64)> {
+    let s = s.trim_start_matches([' ', '\t', ':', '=']);
+    let (first, rest) = s.split_once('/')?;
+    let current = first.trim().parse().ok()?;
+    let total: String = rest
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    Some((current, total.parse().ok()?))
+}
+
+/// Parse a floating-point value from the start of `s`, tolerating separators
+/// and scientific/signed notation (`loss = 1e-3`, `loss=-0.2`).
+fn parse_number(s: &str) -> Option<f64> {
+    let s = s.trim_start_matches([' ', '\t', ':', '=']);
+    let num: String = s
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
+        .collect();
+    num.parse().ok()
+}
+
+#[tauri::command]
+pub async fn start_training<R: Runtime>(
+    app: AppHandle<R>,
+    config: TrainingConfig,
+) -> Result<JobId, String> {
+    let manager = app.state::<JobManager>();
+    let id = manager.next.fetch_add(1, Ordering::SeqCst);
+    let token = CancellationToken::new();
+    let status = Arc::new(Mutex::new(JobStatus {
+        id,
+        state: JobState::Running,
+        step: 0,
+        total: 0,
+        last_loss: None,
+        exit_code: None,
+    }));
+
+    let mut builder = app.shell().command(&config.command).args(&config.args);
+    if let Some(cwd) = &config.cwd {
+        builder = builder.current_dir(cwd);
+    }
+    if !config.env.is_empty() {
+        builder = builder.envs(config.env.clone());
+    }
+    let (mut rx, child) = builder.spawn().map_err(|e| e.to_string())?;
+
+    // Arm hide-to-tray for the whole lifetime of the job, regardless of whether
+    // the frontend has reported a `total` yet.
+    crate::tray::set_job_active(&app, true);
+
+    let task = {
+        let app = app.clone();
+        let token = token.clone();
+        let status = status.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut child = Some(child);
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => {
+                        if let Some(child) = child.take() {
+                            let _ = child.kill();
+                        }
+                        status.lock().unwrap().state = JobState::Cancelled;
+                        crate::tray::set_job_active(&app, false);
+                        // Cancellation retires the job: drop its entry so the
+                        // handle is released cleanly.
+                        app.state::<JobManager>().jobs.lock().unwrap().remove(&id);
+                        break;
+                    }
+                    event = rx.recv() => {
+                        match event {
+                            Some(CommandEvent::Stdout(bytes)) => {
+                                handle_line(&app, id, "stdout", &bytes, &status);
+                            }
+                            Some(CommandEvent::Stderr(bytes)) => {
+                                handle_line(&app, id, "stderr", &bytes, &status);
+                            }
+                            Some(CommandEvent::Terminated(payload)) => {
+                                let mut status = status.lock().unwrap();
+                                status.exit_code = payload.code;
+                                status.state = if payload.code == Some(0) {
+                                    JobState::Completed
+                                } else {
+                                    JobState::Failed
+                                };
+                            }
+                            Some(_) => {}
+                            None => {
+                                crate::tray::set_job_active(&app, false);
+                                // Keep this job's final status but bound overall
+                                // retention so the map can't grow unbounded.
+                                reap_finished(&app);
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    };
+
+    manager.jobs.lock().unwrap().insert(
+        id,
+        JobEntry {
+            token,
+            handle: task,
+            status,
+        },
+    );
+    Ok(id)
+}
+
+fn handle_line<R: Runtime>(
+    app: &AppHandle<R>,
+    job_id: JobId,
+    stream: &'static str,
+    bytes: &[u8],
+    status: &Arc<Mutex<JobStatus>>,
+) {
+    let line = String::from_utf8_lossy(bytes).trim_end().to_string();
+    if line.is_empty() {
+        return;
+    }
+    let (step, loss) = parse_progress(&line);
+    {
+        let mut status = status.lock().unwrap();
+        if let Some((s, t)) = step {
+            status.step = s;
+            status.total = t;
+        }
+        if let Some(l) = loss {
+            status.last_loss = Some(l);
+        }
+    }
+    let _ = app.emit(
+        "training-log",
+        LogEvent {
+            job_id,
+            stream,
+            line,
+        },
+    );
+    if step.is_some() || loss.is_some() {
+        let status = status.lock().unwrap();
+        let _ = app.emit(
+            "training-progress",
+            ProgressEvent {
+                job_id,
+                step: status.step,
+                total: status.total,
+                loss: status.last_loss,
+            },
+        );
+    }
+}
+
+#[tauri::command]
+pub fn cancel_job<R: Runtime>(app: AppHandle<R>, job_id: JobId) -> Result<(), String> {
+    let manager = app.state::<JobManager>();
+    let jobs = manager.jobs.lock().unwrap();
+    match jobs.get(&job_id) {
+        Some(entry) => {
+            entry.token.cancel();
+            Ok(())
+        }
+        None => Err(format!("job `{job_id}` not found")),
+    }
+}
+
+#[tauri::command]
+pub fn get_job_status<R: Runtime>(app: AppHandle<R>, job_id: JobId) -> Result<JobStatus, String> {
+    let manager = app.state::<JobManager>();
+    let jobs = manager.jobs.lock().unwrap();
+    jobs.get(&job_id)
+        .map(|entry| entry.status.lock().unwrap().clone())
+        .ok_or_else(|| format!("job `{job_id}` not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_step_fraction() {
+        let (step, loss) = parse_progress("Epoch 1 step 12/1000");
+        assert_eq!(step, Some((12, 1000)));
+        assert_eq!(loss, None);
+    }
+
+    #[test]
+    fn parses_loss_with_equals_and_colon() {
+        assert_eq!(parse_progress("loss=0.1234").1, Some(0.1234));
+        assert_eq!(parse_progress("train loss: 2.5").1, Some(2.5));
+    }
+
+    #[test]
+    fn parses_step_and_loss_together() {
+        let (step, loss) = parse_progress("step 50/200 loss=0.05");
+        assert_eq!(step, Some((50, 200)));
+        assert_eq!(loss, Some(0.05));
+    }
+
+    #[test]
+    fn tolerates_scientific_and_negative_loss() {
+        assert_eq!(parse_progress("loss = 1e-3").1, Some(0.001));
+        assert_eq!(parse_progress("loss=-0.2").1, Some(-0.2));
+    }
+
+    #[test]
+    fn plain_log_line_yields_nothing() {
+        let (step, loss) = parse_progress("loading dataset from disk");
+        assert_eq!(step, None);
+        assert_eq!(loss, None);
+    }
+
+    #[test]
+    fn incomplete_step_without_total_is_ignored() {
+        assert_eq!(parse_progress("step 12").0, None);
+    }
+
+    #[test]
+    fn tolerates_whitespace_around_slash() {
+        assert_eq!(parse_progress("step 12 / 1000").0, Some((12, 1000)));
+    }
+
+    #[test]
+    fn matches_pluralised_step_keyword() {
+        assert_eq!(parse_progress("steps 3/10").0, Some((3, 10)));
+    }
+
+    #[test]
+    fn does_not_anchor_on_substring() {
+        // "timestep" must not be read as a "step 0/..." progress line.
+        assert_eq!(parse_progress("timestep 5 of the schedule").0, None);
+        // Nor should "loss_scale" be read as a loss value.
+        assert_eq!(parse_progress("loss_scale 128").1, None);
+    }
+}
@@ -1,5 +1,10 @@
 use tauri::Manager;
 
+mod jobs;
+mod tray;
+mod updater;
+mod window_state;
+
 #[tauri::command]
 fn get_app_paths(app: tauri::AppHandle) -> Result<serde_json::Value, String> {
     let app_data_dir = app.path()
@@ -32,7 +37,10 @@ fn maximize_window(window: tauri::Window) {
 
 #[tauri::command]
 fn close_window(window: tauri::Window) {
-    let _ = window.close();
+    // Park the app in the tray instead of exiting while a job is active.
+    if !tray::intercept_close(&window) {
+        let _ = window.close();
+    }
 }
 
 #[tauri::command]
@@ -40,19 +48,155 @@ fn is_maximized(window: tauri::Window) -> bool {
     window.is_maximized().unwrap_or(false)
 }
 
+/// Resolve a window by label, surfacing a descriptive error the UI can show
+/// instead of silently swallowing a missing-window mistake.
+fn window_by_label(
+    app: &tauri::AppHandle,
+    label: &str,
+) -> Result<tauri::WebviewWindow, String> {
+    app.get_webview_window(label)
+        .ok_or_else(|| format!("window `{label}` not found"))
+}
+
+#[tauri::command]
+fn unminimize_window(app: tauri::AppHandle, label: String) -> Result<(), String> {
+    window_by_label(&app, &label)?
+        .unminimize()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn show_window(app: tauri::AppHandle, label: String) -> Result<(), String> {
+    window_by_label(&app, &label)?.show().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn hide_window(app: tauri::AppHandle, label: String) -> Result<(), String> {
+    window_by_label(&app, &label)?.hide().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_always_on_top(
+    app: tauri::AppHandle,
+    label: String,
+    on_top: bool,
+) -> Result<(), String> {
+    window_by_label(&app, &label)?
+        .set_always_on_top(on_top)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn focus_window(app: tauri::AppHandle, label: String) -> Result<(), String> {
+    let window = window_by_label(&app, &label)?;
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn list_windows(app: tauri::AppHandle) -> Vec<String> {
+    app.webview_windows().into_keys().collect()
+}
+
+/// Geometry and chrome options for [`create_window`]. Every field is optional
+/// so the frontend only overrides what it cares about for an auxiliary window.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WindowOptions {
+    title: Option<String>,
+    width: Option<f64>,
+    height: Option<f64>,
+    resizable: Option<bool>,
+    always_on_top: Option<bool>,
+}
+
+#[tauri::command]
+fn create_window(
+    app: tauri::AppHandle,
+    label: String,
+    url: String,
+    options: Option<WindowOptions>,
+) -> Result<(), String> {
+    if app.get_webview_window(&label).is_some() {
+        return Err(format!("window `{label}` already exists"));
+    }
+    let options = options.unwrap_or_default();
+    let mut builder =
+        tauri::WebviewWindowBuilder::new(&app, &label, tauri::WebviewUrl::App(url.into()));
+    if let Some(title) = &options.title {
+        builder = builder.title(title);
+    }
+    if let (Some(width), Some(height)) = (options.width, options.height) {
+        builder = builder.inner_size(width, height);
+    }
+    if let Some(resizable) = options.resizable {
+        builder = builder.resizable(resizable);
+    }
+    if let Some(on_top) = options.always_on_top {
+        builder = builder.always_on_top(on_top);
+    }
+    builder.build().map(|_| ()).map_err(|e| e.to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Long-running commands (training, dataset prep) stream subprocess output
+    // off the main thread, so back the async runtime with a multi-threaded
+    // tokio executor rather than the default current-thread one.
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build tokio runtime");
+    tauri::async_runtime::set(runtime.handle().clone());
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_process::init())
+        .manage(
+            window_state::WindowStateBuilder::new()
+                .auto_show("main")
+                .build(),
+        )
+        .manage(window_state::WindowStateCache::default())
+        .manage(updater::UpdaterConfig::default())
+        .manage(updater::PendingUpdate::default())
+        .manage(tray::TrayState::default())
+        .manage(jobs::JobManager::default())
+        .setup(|app| {
+            window_state::restore_all(app.handle())?;
+            tray::build(app.handle())?;
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            window_state::on_window_event(window, event);
+            tray::on_window_event(window, event);
+        })
         .invoke_handler(tauri::generate_handler![
             get_app_paths,
             minimize_window,
             maximize_window,
             close_window,
-            is_maximized
+            is_maximized,
+            unminimize_window,
+            show_window,
+            hide_window,
+            set_always_on_top,
+            focus_window,
+            list_windows,
+            create_window,
+            window_state::set_window_tracked,
+            window_state::save_window_state,
+            window_state::restore_window_state,
+            updater::check_for_update,
+            updater::download_and_install_update,
+            updater::set_update_endpoints,
+            updater::restart_app,
+            tray::set_tray_status,
+            jobs::start_training,
+            jobs::cancel_job,
+            jobs::get_job_status
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
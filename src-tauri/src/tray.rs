@@ -0,0 +1,149 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{TrayIcon, TrayIconBuilder};
+use tauri::{AppHandle, Emitter, Manager, Window, WindowEvent, Wry};
+
+/// Tray-backed state shared across commands and the window-event hook.
+///
+/// `job_active` gates the hide-to-tray behaviour: while a training job is
+/// running, closing the main window parks the app in the tray instead of
+/// exiting so the run keeps going in the background.
+#[derive(Default)]
+pub struct TrayState {
+    pub job_active: AtomicBool,
+    handles: Mutex<Option<Handles>>,
+}
+
+struct Handles {
+    icon: TrayIcon<Wry>,
+    status: MenuItem<Wry>,
+}
+
+impl TrayState {
+    pub fn job_active(&self) -> bool {
+        self.job_active.load(Ordering::SeqCst)
+    }
+}
+
+/// Explicitly mark whether a background job is running. Driven by the job
+/// lifecycle (see `jobs::start_training`) rather than inferred from progress, so
+/// hide-to-tray is armed the instant a job starts — even before its `total` is
+/// known.
+pub fn set_job_active<R: tauri::Runtime>(app: &AppHandle<R>, active: bool) {
+    app.state::<TrayState>()
+        .job_active
+        .store(active, Ordering::SeqCst);
+}
+
+/// Build the system tray and its menu, storing the handles we later mutate from
+/// [`set_tray_status`]. Called from the builder's `setup` hook.
+pub fn build(app: &AppHandle<Wry>) -> tauri::Result<()> {
+    let show = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
+    let hide = MenuItem::with_id(app, "hide", "Hide", true, None::<&str>)?;
+    let status = MenuItem::with_id(app, "status", "Training: idle", false, None::<&str>)?;
+    let pause = MenuItem::with_id(app, "pause", "Pause", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(
+        app,
+        &[&show, &hide, &status, &separator, &pause, &separator, &quit],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id("main")
+        .menu(&menu)
+        .tooltip("LoRA-Craft")
+        .on_menu_event(on_menu_event);
+    if let Some(icon) = app.default_window_icon().cloned() {
+        builder = builder.icon(icon);
+    }
+    let icon = builder.build(app)?;
+
+    *app.state::<TrayState>().handles.lock().unwrap() = Some(Handles { icon, status });
+    Ok(())
+}
+
+fn on_menu_event(app: &AppHandle<Wry>, event: MenuEvent) {
+    match event.id().as_ref() {
+        "show" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            let _ = app.emit("tray-show", ());
+        }
+        "hide" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.hide();
+            }
+        }
+        "pause" => {
+            let _ = app.emit("tray-pause", ());
+        }
+        "quit" => {
+            let _ = app.emit("tray-quit", ());
+            app.exit(0);
+        }
+        _ => {}
+    }
+}
+
+/// Hide the main window to the tray instead of letting it close the app while
+/// a job is active. Returns `true` if the close was intercepted.
+pub fn intercept_close(window: &Window) -> bool {
+    let app = window.app_handle();
+    let state = app.state::<TrayState>();
+    if window.label() == "main" && state.job_active() {
+        let _ = window.hide();
+        true
+    } else {
+        false
+    }
+}
+
+/// Handle `CloseRequested` for the main window: park it in the tray rather than
+/// exiting when a training job is in flight.
+pub fn on_window_event(window: &Window, event: &WindowEvent) {
+    if let WindowEvent::CloseRequested { api, .. } = event {
+        if intercept_close(window) {
+            api.prevent_close();
+        }
+    }
+}
+
+#[tauri::command]
+pub fn set_tray_status(
+    app: tauri::AppHandle,
+    progress: f32,
+    step: u64,
+    total: u64,
+) -> Result<(), String> {
+    // `job_active` (which arms hide-to-tray) is owned by the job lifecycle via
+    // `set_job_active`, not inferred here. These flags only pick the menu label,
+    // tooltip and tray title.
+    let running = total > 0 && step < total;
+    let completed = total > 0 && step >= total;
+
+    let label = if running {
+        format!("Training: running {:.0}% ({step}/{total})", progress * 100.0)
+    } else if completed {
+        "Training: complete".to_string()
+    } else {
+        "Training: idle".to_string()
+    };
+
+    let state = app.state::<TrayState>();
+    let guard = state.handles.lock().unwrap();
+    if let Some(handles) = guard.as_ref() {
+        handles.status.set_text(&label).map_err(|e| e.to_string())?;
+        handles
+            .icon
+            .set_tooltip(Some(&label))
+            .map_err(|e| e.to_string())?;
+        // On completion, surface a title hint so the user knows to come back.
+        let title = if completed { Some("✓") } else { None };
+        handles.icon.set_title(title).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
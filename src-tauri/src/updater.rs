@@ -0,0 +1,413 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Target triple the running build reports to the release endpoint, e.g.
+/// `darwin-aarch64` / `windows-x86_64`. Kept in the same shape the Tauri
+/// updater uses so existing release manifests stay compatible.
+fn current_target() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        other => other,
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "x86_64",
+        "aarch64" => "aarch64",
+        other => other,
+    };
+    format!("{os}-{arch}")
+}
+
+/// Endpoints and the minisign public key used to authenticate downloads.
+///
+/// Managed in Tauri state so the builder can register the release channel. The
+/// public key is baked in at compile time; if it is missing the updater fails
+/// closed (see [`download_and_install_update`]).
+#[derive(Debug)]
+pub struct UpdaterConfig {
+    /// Release endpoints, settable at runtime via [`set_update_endpoints`] and
+    /// optionally seeded at compile time from `LORA_CRAFT_UPDATER_ENDPOINTS`
+    /// (comma-separated).
+    pub endpoints: Mutex<Vec<String>>,
+    pub pubkey: Option<String>,
+    pub current_version: String,
+}
+
+impl Default for UpdaterConfig {
+    fn default() -> Self {
+        let endpoints = option_env!("LORA_CRAFT_UPDATER_ENDPOINTS")
+            .map(|raw| {
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            endpoints: Mutex::new(endpoints),
+            pubkey: option_env!("LORA_CRAFT_UPDATER_PUBKEY").map(str::to_string),
+            current_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// One platform entry inside a release manifest.
+#[derive(Debug, Clone, Deserialize)]
+struct ManifestPlatform {
+    url: String,
+    /// Detached minisign signature of the artifact at `url`.
+    signature: String,
+}
+
+/// Release manifest fetched from an endpoint, mirroring the Tauri updater JSON.
+#[derive(Debug, Clone, Deserialize)]
+struct Manifest {
+    version: String,
+    #[serde(default)]
+    notes: String,
+    #[serde(default, rename = "pub_date")]
+    pub_date: Option<String>,
+    platforms: std::collections::HashMap<String, ManifestPlatform>,
+}
+
+/// Result of an update check surfaced to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub available: bool,
+    pub version: String,
+    pub notes: String,
+    pub pub_date: Option<String>,
+}
+
+/// Progress payload emitted as `update-progress` while the artifact downloads.
+#[derive(Debug, Clone, Serialize)]
+struct DownloadProgress {
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+/// Most recently matched manifest, cached by [`check_for_update`] so the
+/// install step downloads exactly what the user was shown.
+#[derive(Debug, Default)]
+pub struct PendingUpdate(pub Mutex<Option<PendingArtifact>>);
+
+#[derive(Debug, Clone)]
+pub struct PendingArtifact {
+    #[allow(dead_code)]
+    version: String,
+    url: String,
+    signature: String,
+}
+
+fn expand(template: &str, current_version: &str) -> String {
+    template
+        .replace("{{target}}", &current_target())
+        .replace("{{current_version}}", current_version)
+}
+
+fn is_newer(candidate: &str, current: &str) -> Result<bool, String> {
+    let candidate = semver::Version::parse(candidate.trim_start_matches('v'))
+        .map_err(|e| format!("invalid manifest version `{candidate}`: {e}"))?;
+    let current = semver::Version::parse(current.trim_start_matches('v'))
+        .map_err(|e| format!("invalid current version `{current}`: {e}"))?;
+    Ok(candidate > current)
+}
+
+#[tauri::command]
+pub async fn check_for_update<R: Runtime>(app: AppHandle<R>) -> Result<UpdateInfo, String> {
+    let (endpoints, current_version) = {
+        let config = app.state::<UpdaterConfig>();
+        let endpoints = config.endpoints.lock().unwrap().clone();
+        (endpoints, config.current_version.clone())
+    };
+    let target = current_target();
+    let client = reqwest::Client::new();
+
+    for endpoint in &endpoints {
+        let url = expand(endpoint, &current_version);
+        let response = match client.get(&url).send().await {
+            Ok(response) if response.status().is_success() => response,
+            _ => continue,
+        };
+        let Ok(manifest) = response.json::<Manifest>().await else {
+            continue;
+        };
+        // A malformed version from one endpoint shouldn't abort the whole
+        // check; skip it and try the next.
+        match is_newer(&manifest.version, &current_version) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(err) => {
+                eprintln!("updater: skipping endpoint {url}: {err}");
+                continue;
+            }
+        }
+        let Some(platform) = manifest.platforms.get(&target) else {
+            continue;
+        };
+        *app.state::<PendingUpdate>().0.lock().unwrap() = Some(PendingArtifact {
+            version: manifest.version.clone(),
+            url: platform.url.clone(),
+            signature: platform.signature.clone(),
+        });
+        return Ok(UpdateInfo {
+            available: true,
+            version: manifest.version,
+            notes: manifest.notes,
+            pub_date: manifest.pub_date,
+        });
+    }
+
+    Ok(UpdateInfo {
+        available: false,
+        version: current_version,
+        notes: String::new(),
+        pub_date: None,
+    })
+}
+
+#[tauri::command]
+pub async fn download_and_install_update<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let pubkey = {
+        let config = app.state::<UpdaterConfig>();
+        config
+            .pubkey
+            .clone()
+            .ok_or_else(|| "updater public key is not configured; refusing to install".to_string())?
+    };
+    let artifact = app
+        .state::<PendingUpdate>()
+        .0
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "no pending update; call check_for_update first".to_string())?;
+
+    let client = reqwest::Client::new();
+    let mut response = client
+        .get(&artifact.url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?;
+    let total = response.content_length();
+    let mut downloaded: u64 = 0;
+    let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        downloaded += chunk.len() as u64;
+        bytes.extend_from_slice(&chunk);
+        let _ = app.emit("update-progress", DownloadProgress { downloaded, total });
+    }
+
+    // Signature verification is mandatory: a compromised mirror that serves a
+    // tampered binary must not be able to get it installed.
+    verify_signature(&pubkey, &artifact.signature, &bytes)?;
+
+    apply_update(&app, &artifact.url, &bytes)?;
+    *app.state::<PendingUpdate>().0.lock().unwrap() = None;
+    Ok(())
+}
+
+/// Verify `bytes` against a detached minisign `signature` produced with the
+/// secret key matching `pubkey`. Any failure is fatal.
+///
+/// The manifest mirrors the Tauri updater JSON, whose `signature` field is the
+/// base64-encoded contents of the `.minisig` file — not the raw minisign text
+/// `Signature::decode` wants. So we base64-decode first, then parse the
+/// two-line (`untrusted comment:` + base64) form.
+fn verify_signature(pubkey: &str, signature: &str, bytes: &[u8]) -> Result<(), String> {
+    use base64::Engine;
+
+    let public_key = minisign_verify::PublicKey::from_base64(pubkey)
+        .map_err(|e| format!("invalid updater public key: {e}"))?;
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(signature.trim())
+        .map_err(|e| format!("malformed update signature encoding: {e}"))?;
+    let minisig = String::from_utf8(decoded)
+        .map_err(|e| format!("update signature is not valid UTF-8: {e}"))?;
+    let signature = minisign_verify::Signature::decode(&minisig)
+        .map_err(|e| format!("malformed update signature: {e}"))?;
+    public_key
+        .verify(bytes, &signature, false)
+        .map_err(|e| format!("update signature verification failed: {e}"))
+}
+
+/// Install the verified artifact by handing it off to the right platform
+/// installer based on its extension.
+///
+/// The downloaded bytes are an installer/bundle — `.msi`/`.exe` on Windows,
+/// `.dmg`/`.pkg` on macOS, `.AppImage`/`.deb`/`.rpm` on Linux. Any other type,
+/// including a macOS `.app.tar.gz` archive, is rejected with an error before
+/// anything is launched.
+/// Most of these are not directly executable, so we stage the file under
+/// `app_config_dir` (preserving the URL filename so the extension survives) and
+/// dispatch to the matching installer command. Only self-contained executables
+/// (`.exe`, `.AppImage`) are launched directly. This only runs once the
+/// signature has been verified, so a tampered artifact is never executed, and
+/// the process exits afterwards so the installer can replace the running bundle.
+fn apply_update<R: Runtime>(app: &AppHandle<R>, url: &str, bytes: &[u8]) -> Result<(), String> {
+    let filename = url
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("pending-update.bin");
+    let staging = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| e.to_string())?
+        .join(filename);
+    if let Some(parent) = staging.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&staging, bytes).map_err(|e| e.to_string())?;
+
+    let mut installer = installer_command(&staging, filename)?;
+
+    #[cfg(unix)]
+    if installer.get_program() == staging.as_os_str() {
+        // We're launching the artifact itself; make sure it's executable.
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&staging)
+            .map_err(|e| e.to_string())?
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&staging, perms).map_err(|e| e.to_string())?;
+    }
+
+    // Launch the installer detached, then exit so it can swap the running bundle.
+    installer
+        .spawn()
+        .map_err(|e| format!("failed to launch update installer: {e}"))?;
+    app.exit(0);
+    Ok(())
+}
+
+/// Build the command that installs `staging` according to its `filename`
+/// extension. Returns an error for artifact types we don't know how to install
+/// rather than blindly trying to execute them.
+fn installer_command(
+    staging: &std::path::Path,
+    filename: &str,
+) -> Result<std::process::Command, String> {
+    let lower = filename.to_lowercase();
+    let ext = lower.rsplit('.').next().unwrap_or("");
+    let mut command = match ext {
+        // Directly executable installers/bundles.
+        "exe" | "appimage" => std::process::Command::new(staging),
+        // Windows installer package.
+        "msi" => {
+            let mut c = std::process::Command::new("msiexec");
+            c.arg("/i").arg(staging);
+            c
+        }
+        // macOS disk image — mount it; the UI walks the user through the rest.
+        "dmg" => {
+            let mut c = std::process::Command::new("hdiutil");
+            c.arg("attach").arg(staging);
+            c
+        }
+        // macOS installer package.
+        "pkg" => {
+            let mut c = std::process::Command::new("installer");
+            c.arg("-pkg").arg(staging).arg("-target").arg("/");
+            c
+        }
+        // Debian package.
+        "deb" => {
+            let mut c = std::process::Command::new("dpkg");
+            c.arg("-i").arg(staging);
+            c
+        }
+        // RPM package.
+        "rpm" => {
+            let mut c = std::process::Command::new("rpm");
+            c.arg("-U").arg(staging);
+            c
+        }
+        other => {
+            return Err(format!(
+                "don't know how to install update artifact `{filename}` (unsupported type `{other}`)"
+            ))
+        }
+    };
+    // Ensure a consistent working directory for the spawned installer.
+    if let Some(parent) = staging.parent() {
+        command.current_dir(parent);
+    }
+    Ok(command)
+}
+
+/// Register (replace) the release endpoints the updater polls. Lets the
+/// frontend configure the channel at runtime when it isn't baked in at compile
+/// time via `LORA_CRAFT_UPDATER_ENDPOINTS`.
+#[tauri::command]
+pub fn set_update_endpoints<R: Runtime>(app: AppHandle<R>, endpoints: Vec<String>) {
+    *app.state::<UpdaterConfig>().endpoints.lock().unwrap() = endpoints;
+}
+
+#[tauri::command]
+pub fn restart_app<R: Runtime>(app: AppHandle<R>) {
+    app.restart();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newer_patch_is_detected() {
+        assert!(is_newer("1.2.4", "1.2.3").unwrap());
+    }
+
+    #[test]
+    fn equal_versions_are_not_newer() {
+        assert!(!is_newer("1.2.3", "1.2.3").unwrap());
+    }
+
+    #[test]
+    fn older_version_is_not_newer() {
+        assert!(!is_newer("1.2.0", "1.2.3").unwrap());
+    }
+
+    #[test]
+    fn leading_v_is_tolerated() {
+        assert!(is_newer("v2.0.0", "1.9.9").unwrap());
+        assert!(!is_newer("v1.0.0", "v1.0.0").unwrap());
+    }
+
+    #[test]
+    fn invalid_versions_error() {
+        assert!(is_newer("not-a-version", "1.0.0").is_err());
+    }
+
+    // Test-only key pair and a prehashed (`ED`) minisign signature over the
+    // bytes `b"test"`. We wrap the raw `.minisig` text in base64 to match the
+    // Tauri manifest `signature` encoding the real path consumes.
+    const PUBKEY: &str = "RWR1mbkJ1i/DfO/9+BwlQNgJaBHAmpcm9TgbnTjL+tj8WGQSVm5XGGQc";
+    const MINISIG: &str = "untrusted comment: signature from minisign secret key\nRUR1mbkJ1i/DfMZQk5RXcWw3dh1zBQh/rD6AqeHbuw1HFBhwcgpZQsxSdKOQc7x181iBMn7tg59UHy5ab4qBYlqEMDh+3eYdGgM=\ntrusted comment: timestamp:1555779966\tfile:test\nOoxn8ksoNtfXA1OmHXKl/Bd7UvooMVUIfwVgdQVDuRfdTz+PXo1WkxXJ7PtDKuMWTQGZpZ5q1ZeJV1Qd399ODA==";
+
+    fn tauri_signature() -> String {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.encode(MINISIG)
+    }
+
+    #[test]
+    fn verifies_known_good_artifact() {
+        assert!(verify_signature(PUBKEY, &tauri_signature(), b"test").is_ok());
+    }
+
+    #[test]
+    fn rejects_tampered_artifact() {
+        assert!(verify_signature(PUBKEY, &tauri_signature(), b"tampered").is_err());
+    }
+
+    #[test]
+    fn rejects_non_base64_signature() {
+        // A raw (un-base64'd) minisig must be refused: the manifest always
+        // carries the base64-wrapped form.
+        assert!(verify_signature(PUBKEY, MINISIG, b"test").is_err());
+    }
+}
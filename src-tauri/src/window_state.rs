@@ -0,0 +1,411 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Manager, PhysicalPosition, PhysicalSize, Runtime, WebviewWindow, Window,
+    WindowEvent,
+};
+
+/// Name of the file the per-window geometry is persisted to, kept under the
+/// resolved `app_config_dir` alongside the rest of the app configuration.
+const STATE_FILENAME: &str = "window-state.json";
+
+/// Serialized geometry of a single labeled window.
+///
+/// Mirrors the shape persisted by `tauri-plugin-window-state` so the frontend
+/// can reason about it the same way: outer position, inner size, the maximized
+/// flag and (optionally) the monitor the window last lived on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<String>,
+}
+
+/// Opt-in/opt-out configuration for the window-state subsystem.
+///
+/// Managed in Tauri state so both the builder (compile time, via
+/// [`WindowStateBuilder`]) and the frontend (runtime, via
+/// [`set_window_tracked`]) can register which windows participate. Windows in
+/// `denylist` are never persisted or restored; windows in `skip_initial` are
+/// persisted but not restored on the next launch; windows in `auto_show` are
+/// hidden, restored, then shown by [`restore_all`] so their geometry is in
+/// place before they become visible. The sets use interior mutability so the
+/// frontend can flip them after launch.
+#[derive(Debug, Default)]
+pub struct WindowStateFlags {
+    denylist: Mutex<HashSet<String>>,
+    skip_initial: Mutex<HashSet<String>>,
+    auto_show: Mutex<HashSet<String>>,
+}
+
+/// Compile-time configuration of the window-state subsystem, mirroring
+/// `tauri-plugin-window-state`'s `WindowStateBuilder`. Build one in `run()` and
+/// hand the result to `.manage(...)`.
+#[derive(Debug, Default)]
+pub struct WindowStateBuilder {
+    denylist: HashSet<String>,
+    skip_initial: HashSet<String>,
+    auto_show: HashSet<String>,
+}
+
+impl WindowStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Exclude `label` from persistence/restoration entirely.
+    pub fn deny(mut self, label: impl Into<String>) -> Self {
+        self.denylist.insert(label.into());
+        self
+    }
+
+    /// Persist `label` but don't restore it on the next launch.
+    pub fn skip_initial(mut self, label: impl Into<String>) -> Self {
+        self.skip_initial.insert(label.into());
+        self
+    }
+
+    /// Restore `label` while hidden and reveal it afterwards, guaranteeing the
+    /// geometry is applied before the window is shown regardless of its
+    /// configured initial visibility.
+    pub fn auto_show(mut self, label: impl Into<String>) -> Self {
+        self.auto_show.insert(label.into());
+        self
+    }
+
+    pub fn build(self) -> WindowStateFlags {
+        WindowStateFlags {
+            denylist: Mutex::new(self.denylist),
+            skip_initial: Mutex::new(self.skip_initial),
+            auto_show: Mutex::new(self.auto_show),
+        }
+    }
+}
+
+/// In-memory cache of the last geometry read from / written to disk.
+#[derive(Debug, Default)]
+pub struct WindowStateCache(pub Mutex<HashMap<String, WindowState>>);
+
+impl WindowStateFlags {
+    fn tracked(&self, label: &str) -> bool {
+        !self.denylist.lock().unwrap().contains(label)
+    }
+
+    fn skip_initial(&self, label: &str) -> bool {
+        self.skip_initial.lock().unwrap().contains(label)
+    }
+
+    fn auto_show(&self, label: &str) -> bool {
+        self.auto_show.lock().unwrap().contains(label)
+    }
+}
+
+fn state_path<R: Runtime>(app: &AppHandle<R>) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(STATE_FILENAME))
+}
+
+fn read_from_disk<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, WindowState> {
+    let Ok(path) = state_path(app) else {
+        return HashMap::new();
+    };
+    match std::fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn capture<R: Runtime>(window: &Window<R>) -> Option<WindowState> {
+    let maximized = window.is_maximized().unwrap_or(false);
+    // When maximized, the OS reports the maximized geometry; keep the previously
+    // stored "restored" size so un-maximizing on next launch is sensible.
+    let size = window.inner_size().ok()?;
+    let position = window.outer_position().ok()?;
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+    Some(WindowState {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized,
+        monitor,
+    })
+}
+
+/// Persist the current geometry of `window` into the in-memory cache.
+///
+/// The cache is flushed to disk lazily on [`save_all`] (and on close) to avoid
+/// hammering the filesystem on every `Moved`/`Resized` event.
+pub fn remember<R: Runtime>(window: &Window<R>) {
+    let app = window.app_handle();
+    let label = window.label().to_string();
+    let flags = app.state::<WindowStateFlags>();
+    if !flags.tracked(&label) {
+        return;
+    }
+    if let Some(state) = capture(window) {
+        let cache = app.state::<WindowStateCache>();
+        let mut map = cache.0.lock().unwrap();
+        // Preserve the restored size while maximized so it survives round-trips.
+        if state.maximized {
+            if let Some(previous) = map.get(&label) {
+                let mut merged = state;
+                merged.width = previous.width;
+                merged.height = previous.height;
+                map.insert(label, merged);
+                return;
+            }
+        }
+        map.insert(label, state);
+    }
+}
+
+/// Flush the cached geometry for every tracked window to `window-state.json`.
+pub fn save_all<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    for window in app.webview_windows().values() {
+        remember(window.as_ref());
+    }
+    let cache = app.state::<WindowStateCache>();
+    let map = cache.0.lock().unwrap();
+    let path = state_path(app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let bytes = serde_json::to_vec_pretty(&*map).map_err(|e| e.to_string())?;
+    std::fs::write(&path, bytes).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Bounds of a single monitor, in physical pixels.
+#[derive(Debug, Clone, Copy)]
+struct MonitorRect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// Clamp a saved geometry to the bounds of a currently-available monitor so a
+/// window stored on a now-disconnected display doesn't open off-screen.
+///
+/// Pure helper over plain rectangles so the off-screen logic is unit-testable
+/// without a live window; `primary` is the monitor to re-home onto when the
+/// saved geometry no longer intersects anything.
+fn clamp_geometry(
+    state: &WindowState,
+    monitors: &[MonitorRect],
+    primary: Option<&MonitorRect>,
+) -> WindowState {
+    if monitors.is_empty() {
+        return state.clone();
+    }
+    // A window is visible if it intersects any available monitor.
+    let intersects = monitors.iter().any(|m| {
+        state.x < m.x + m.width as i32
+            && state.x + state.width as i32 > m.x
+            && state.y < m.y + m.height as i32
+            && state.y + state.height as i32 > m.y
+    });
+    if intersects {
+        return state.clone();
+    }
+    // Otherwise re-home the window on the primary monitor's origin.
+    let mut clamped = state.clone();
+    if let Some(monitor) = primary.or_else(|| monitors.first()) {
+        clamped.x = monitor.x;
+        clamped.y = monitor.y;
+    }
+    clamped
+}
+
+fn clamp_to_monitors<R: Runtime>(window: &WebviewWindow<R>, state: &WindowState) -> WindowState {
+    let monitors: Vec<MonitorRect> = window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|m| {
+            let pos = m.position();
+            let size = m.size();
+            MonitorRect {
+                x: pos.x,
+                y: pos.y,
+                width: size.width,
+                height: size.height,
+            }
+        })
+        .collect();
+    let primary = window.primary_monitor().ok().flatten().map(|m| {
+        let pos = m.position();
+        let size = m.size();
+        MonitorRect {
+            x: pos.x,
+            y: pos.y,
+            width: size.width,
+            height: size.height,
+        }
+    });
+    clamp_geometry(state, &monitors, primary.as_ref())
+}
+
+/// Restore the saved geometry of a single window before it is shown.
+pub fn restore<R: Runtime>(window: &WebviewWindow<R>) -> Result<(), String> {
+    let app = window.app_handle();
+    let label = window.label().to_string();
+    let flags = app.state::<WindowStateFlags>();
+    if !flags.tracked(&label) || flags.skip_initial(&label) {
+        return Ok(());
+    }
+    let cache = app.state::<WindowStateCache>();
+    let state = cache.0.lock().unwrap().get(&label).cloned();
+    let Some(state) = state else {
+        return Ok(());
+    };
+    let state = clamp_to_monitors(window, &state);
+    window
+        .set_position(PhysicalPosition::new(state.x, state.y))
+        .map_err(|e| e.to_string())?;
+    window
+        .set_size(PhysicalSize::new(state.width, state.height))
+        .map_err(|e| e.to_string())?;
+    if state.maximized {
+        window.maximize().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Load persisted state from disk and restore every tracked window.
+///
+/// Called from the builder's `setup` hook, which runs *after* config-defined
+/// windows are created. For windows opted into `auto_show` (see
+/// [`WindowStateBuilder::auto_show`]) the restore-before-show guarantee is
+/// enforced in code: the window is hidden, its geometry applied, then shown —
+/// so there is no visible jump regardless of its configured visibility. Windows
+/// not opted in keep whatever initial visibility their author chose and are
+/// only repositioned.
+pub fn restore_all<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let loaded = read_from_disk(app);
+    {
+        let cache = app.state::<WindowStateCache>();
+        *cache.0.lock().unwrap() = loaded;
+    }
+    let flags = app.state::<WindowStateFlags>();
+    for window in app.webview_windows().values() {
+        let label = window.label();
+        if !flags.tracked(label) || flags.skip_initial(label) {
+            continue;
+        }
+        if flags.auto_show(label) {
+            // Hide first so the geometry change can't be seen, then reveal.
+            window.hide().map_err(|e| e.to_string())?;
+            restore(window)?;
+            window.show().map_err(|e| e.to_string())?;
+        } else {
+            restore(window)?;
+        }
+    }
+    Ok(())
+}
+
+/// Handle `Moved`/`Resized`/`CloseRequested` for tracked windows, caching
+/// geometry as it changes and flushing on close.
+pub fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => remember(window),
+        WindowEvent::CloseRequested { .. } => {
+            remember(window);
+            let _ = save_all(&window.app_handle());
+        }
+        _ => {}
+    }
+}
+
+/// Opt a window in or out of geometry tracking at runtime, letting the frontend
+/// control which windows are remembered without a rebuild.
+#[tauri::command]
+pub fn set_window_tracked(app: tauri::AppHandle, label: String, tracked: bool) {
+    let flags = app.state::<WindowStateFlags>();
+    let mut denylist = flags.denylist.lock().unwrap();
+    if tracked {
+        denylist.remove(&label);
+    } else {
+        denylist.insert(label);
+    }
+}
+
+#[tauri::command]
+pub fn save_window_state(app: tauri::AppHandle) -> Result<(), String> {
+    save_all(&app)
+}
+
+#[tauri::command]
+pub fn restore_window_state(window: tauri::WebviewWindow) -> Result<(), String> {
+    restore(&window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(x: i32, y: i32) -> WindowState {
+        WindowState {
+            x,
+            y,
+            width: 800,
+            height: 600,
+            maximized: false,
+            monitor: None,
+        }
+    }
+
+    fn monitor(x: i32, y: i32) -> MonitorRect {
+        MonitorRect {
+            x,
+            y,
+            width: 1920,
+            height: 1080,
+        }
+    }
+
+    #[test]
+    fn on_screen_geometry_is_left_untouched() {
+        let monitors = [monitor(0, 0)];
+        let saved = state(100, 100);
+        let clamped = clamp_geometry(&saved, &monitors, monitors.first());
+        assert_eq!((clamped.x, clamped.y), (100, 100));
+    }
+
+    #[test]
+    fn partially_visible_geometry_counts_as_on_screen() {
+        let monitors = [monitor(0, 0)];
+        // Bottom-right corner still overlaps the monitor at (0,0).
+        let saved = state(1900, 1060);
+        let clamped = clamp_geometry(&saved, &monitors, monitors.first());
+        assert_eq!((clamped.x, clamped.y), (1900, 1060));
+    }
+
+    #[test]
+    fn off_screen_geometry_is_rehomed_onto_primary() {
+        // Saved on a second monitor at (1920, 0) that is no longer connected.
+        let monitors = [monitor(0, 0)];
+        let saved = state(3000, 500);
+        let clamped = clamp_geometry(&saved, &monitors, monitors.first());
+        assert_eq!((clamped.x, clamped.y), (0, 0));
+    }
+
+    #[test]
+    fn without_monitors_geometry_is_preserved() {
+        let saved = state(3000, 500);
+        let clamped = clamp_geometry(&saved, &[], None);
+        assert_eq!((clamped.x, clamped.y), (3000, 500));
+    }
+}